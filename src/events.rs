@@ -0,0 +1,57 @@
+//! The event multiplexing layer for the main loop: a single `Event` enum fed
+//! by independent background threads (crossterm input, a redraw ticker, and
+//! one PTY reader per pane — see `main::read_into_grid`) over an unbounded
+//! channel. `main::run_app` just does `rx.recv()` and dispatches, so none of
+//! the threads ever block waiting on each other.
+
+use crossterm::event::{self, Event as CEvent, KeyEvent};
+use std::sync::mpsc::Sender;
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+pub enum Event {
+    Key(KeyEvent),
+    Resize(u16, u16),
+    PtyOutput(usize),
+    ChildExit(usize, u32),
+    Tick,
+}
+
+/// Translates crossterm input into `Event`s on a dedicated thread.
+pub fn spawn_input_thread(tx: Sender<Event>) {
+    std::thread::spawn(move || loop {
+        match event::poll(Duration::from_millis(1000)) {
+            Ok(true) => {
+                let sent = match event::read() {
+                    Ok(CEvent::Key(key)) => tx.send(Event::Key(key)).is_ok(),
+                    Ok(CEvent::Resize(w, h)) => tx.send(Event::Resize(w, h)).is_ok(),
+                    Ok(_) => true,
+                    Err(_) => false,
+                };
+                if !sent {
+                    return;
+                }
+            }
+            Ok(false) => {}
+            Err(_) => return,
+        }
+    });
+}
+
+/// Sends `Event::Tick` at roughly `tick_rate`, driving throttled redraws and
+/// the periodic child-exit check in `main::handle_event`.
+pub fn spawn_ticker(tx: Sender<Event>, tick_rate: Duration) {
+    std::thread::spawn(move || {
+        let mut last = Instant::now();
+        loop {
+            let elapsed = last.elapsed();
+            if elapsed < tick_rate {
+                std::thread::sleep(tick_rate - elapsed);
+            }
+            last = Instant::now();
+            if tx.send(Event::Tick).is_err() {
+                return;
+            }
+        }
+    });
+}
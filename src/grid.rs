@@ -0,0 +1,623 @@
+//! A small VT100/ANSI terminal grid model.
+//!
+//! `Parser` consumes raw bytes read from a `Terminal` PTY and feeds a `Grid`
+//! of styled `Cell`s plus a cursor position and scrollback buffer. It covers
+//! the escape sequences real-world shells and TUIs actually emit: cursor
+//! movement, erase, SGR styling, a scroll region, OSC/DCS/SOS/PM/APC string
+//! sequences (window titles, OSC 133 shell-integration markers), and UTF-8
+//! text. Anything else unrecognized is swallowed rather than surfaced,
+//! matching how terminal emulators stay silent on unsupported sequences
+//! instead of corrupting the screen.
+
+use ratatui::style::{Color, Modifier, Style};
+use std::collections::VecDeque;
+
+const MAX_SCROLLBACK: usize = 2000;
+/// Cap on an accumulated CSI parameter, matching real terminal parsers —
+/// keeps a long run of digit bytes from overflowing the accumulator or the
+/// cursor arithmetic it later feeds into.
+const MAX_CSI_PARAM: u16 = 9999;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    pub style: CellStyle,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            ch: ' ',
+            style: CellStyle::default(),
+        }
+    }
+}
+
+/// A reduced, `Eq`-able mirror of `ratatui::style::Style` so `Cell` can derive
+/// `PartialEq` (ratatui's `Color` isn't `Eq`-friendly across all variants in
+/// older versions, so we keep our own small copy).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct CellStyle {
+    pub fg: Option<NamedColor>,
+    pub bg: Option<NamedColor>,
+    pub bold: bool,
+    pub reversed: bool,
+}
+
+impl CellStyle {
+    pub fn to_ratatui(self) -> Style {
+        let mut style = Style::default();
+        if let Some(fg) = self.fg {
+            style = style.fg(fg.into());
+        }
+        if let Some(bg) = self.bg {
+            style = style.bg(bg.into());
+        }
+        if self.bold {
+            style = style.add_modifier(Modifier::BOLD);
+        }
+        if self.reversed {
+            style = style.add_modifier(Modifier::REVERSED);
+        }
+        style
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NamedColor {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+}
+
+impl From<NamedColor> for Color {
+    fn from(c: NamedColor) -> Color {
+        match c {
+            NamedColor::Black => Color::Black,
+            NamedColor::Red => Color::Red,
+            NamedColor::Green => Color::Green,
+            NamedColor::Yellow => Color::Yellow,
+            NamedColor::Blue => Color::Blue,
+            NamedColor::Magenta => Color::Magenta,
+            NamedColor::Cyan => Color::Cyan,
+            NamedColor::White => Color::White,
+            NamedColor::BrightBlack => Color::DarkGray,
+            NamedColor::BrightRed => Color::LightRed,
+            NamedColor::BrightGreen => Color::LightGreen,
+            NamedColor::BrightYellow => Color::LightYellow,
+            NamedColor::BrightBlue => Color::LightBlue,
+            NamedColor::BrightMagenta => Color::LightMagenta,
+            NamedColor::BrightCyan => Color::LightCyan,
+            NamedColor::BrightWhite => Color::White,
+        }
+    }
+}
+
+fn color_from_sgr(code: u16) -> Option<NamedColor> {
+    use NamedColor::*;
+    Some(match code {
+        30 | 40 => Black,
+        31 | 41 => Red,
+        32 | 42 => Green,
+        33 | 43 => Yellow,
+        34 | 44 => Blue,
+        35 | 45 => Magenta,
+        36 | 46 => Cyan,
+        37 | 47 => White,
+        90 | 100 => BrightBlack,
+        91 | 101 => BrightRed,
+        92 | 102 => BrightGreen,
+        93 | 103 => BrightYellow,
+        94 | 104 => BrightBlue,
+        95 | 105 => BrightMagenta,
+        96 | 106 => BrightCyan,
+        97 | 107 => BrightWhite,
+        _ => return None,
+    })
+}
+
+/// A fixed-size grid of cells backing one pane's screen, plus a scrollback
+/// ring of rows that have scrolled off the top.
+#[derive(Debug)]
+pub struct Grid {
+    rows: Vec<Vec<Cell>>,
+    pub scrollback: VecDeque<Vec<Cell>>,
+    pub cursor_row: u16,
+    pub cursor_col: u16,
+    pub width: u16,
+    pub height: u16,
+    scroll_top: u16,
+    scroll_bottom: u16,
+}
+
+impl Grid {
+    pub fn new(width: u16, height: u16) -> Self {
+        let width = width.max(1);
+        let height = height.max(1);
+        Self {
+            rows: vec![vec![Cell::default(); width as usize]; height as usize],
+            scrollback: VecDeque::new(),
+            cursor_row: 0,
+            cursor_col: 0,
+            width,
+            height,
+            scroll_top: 0,
+            scroll_bottom: height.saturating_sub(1),
+        }
+    }
+
+    pub fn resize(&mut self, width: u16, height: u16) {
+        let width = width.max(1);
+        let height = height.max(1);
+        self.rows.resize(height as usize, vec![Cell::default(); width as usize]);
+        for row in &mut self.rows {
+            row.resize(width as usize, Cell::default());
+        }
+        self.width = width;
+        self.height = height;
+        self.scroll_top = 0;
+        self.scroll_bottom = height.saturating_sub(1);
+        self.clamp_cursor();
+    }
+
+    pub fn row(&self, idx: u16) -> &[Cell] {
+        &self.rows[idx as usize]
+    }
+
+    /// Returns the rows that should be on screen given a scroll offset (in
+    /// lines, counted up from the bottom), stitching scrollback history onto
+    /// the live grid the same way a terminal emulator's viewport does.
+    pub fn visible_rows(&self, offset: u16) -> Vec<Vec<Cell>> {
+        let mut all: Vec<Vec<Cell>> = self.scrollback.iter().cloned().collect();
+        all.extend(self.rows.iter().cloned());
+        let total = all.len();
+        let height = self.height as usize;
+        let start = total
+            .saturating_sub(height)
+            .saturating_sub(offset as usize);
+        let end = (start + height).min(total);
+        all[start..end].to_vec()
+    }
+
+    fn clamp_cursor(&mut self) {
+        self.cursor_row = self.cursor_row.min(self.height.saturating_sub(1));
+        self.cursor_col = self.cursor_col.min(self.width.saturating_sub(1));
+    }
+
+    fn scroll_up_one(&mut self) {
+        let top = self.scroll_top as usize;
+        let bottom = self.scroll_bottom as usize;
+        if top == 0 {
+            let removed = self.rows.remove(0);
+            self.scrollback.push_back(removed);
+            if self.scrollback.len() > MAX_SCROLLBACK {
+                self.scrollback.pop_front();
+            }
+            self.rows.insert(bottom, vec![Cell::default(); self.width as usize]);
+        } else {
+            self.rows.remove(top);
+            self.rows.insert(bottom, vec![Cell::default(); self.width as usize]);
+        }
+    }
+
+    fn newline(&mut self) {
+        if self.cursor_row == self.scroll_bottom {
+            self.scroll_up_one();
+        } else {
+            self.cursor_row = (self.cursor_row + 1).min(self.height.saturating_sub(1));
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum State {
+    Ground,
+    Escape,
+    Csi,
+    /// Inside an OSC/DCS/SOS/PM/APC string sequence, swallowing bytes until
+    /// BEL or ST (`ESC \`) terminates it.
+    Str,
+    /// Saw an ESC while in `Str`; one more byte tells us whether that was ST
+    /// or just a stray ESC inside the string body.
+    StrEscape,
+    /// Saw `ESC (` or `ESC )` (charset designation); the next byte is the
+    /// charset and then we're back to Ground.
+    Charset,
+}
+
+/// Feeds raw PTY bytes into a `Grid`, interpreting CSI/SGR escape sequences.
+pub struct Parser {
+    state: State,
+    params: Vec<u16>,
+    current_param: Option<u16>,
+    style: CellStyle,
+    /// Bytes of a multi-byte UTF-8 sequence seen so far, buffered across
+    /// `advance` calls in case a codepoint is split across PTY reads.
+    utf8_buf: Vec<u8>,
+    utf8_pending: usize,
+}
+
+impl Parser {
+    pub fn new() -> Self {
+        Self {
+            state: State::Ground,
+            params: Vec::new(),
+            current_param: None,
+            style: CellStyle::default(),
+            utf8_buf: Vec::new(),
+            utf8_pending: 0,
+        }
+    }
+
+    pub fn advance(&mut self, grid: &mut Grid, bytes: &[u8]) {
+        for &byte in bytes {
+            self.advance_byte(grid, byte);
+        }
+    }
+
+    fn advance_byte(&mut self, grid: &mut Grid, byte: u8) {
+        match self.state {
+            State::Ground => self.advance_ground(grid, byte),
+            State::Escape => self.advance_escape(grid, byte),
+            State::Csi => self.advance_csi(grid, byte),
+            State::Str => self.advance_str(byte),
+            State::StrEscape => self.advance_str_escape(byte),
+            State::Charset => self.state = State::Ground,
+        }
+    }
+
+    fn advance_ground(&mut self, grid: &mut Grid, byte: u8) {
+        match byte {
+            0x1b => self.state = State::Escape,
+            b'\r' => grid.cursor_col = 0,
+            b'\n' => grid.newline(),
+            0x08 => grid.cursor_col = grid.cursor_col.saturating_sub(1),
+            0x07 => {} // bell
+            0x09 => {
+                let next_tab = (grid.cursor_col / 8 + 1) * 8;
+                grid.cursor_col = next_tab.min(grid.width.saturating_sub(1));
+            }
+            _ => self.print_byte(grid, byte),
+        }
+    }
+
+    /// Buffers `byte` as part of a (possibly multi-byte) UTF-8 codepoint,
+    /// decoding and placing it once the full sequence has arrived. PTY reads
+    /// are chunked arbitrarily, so a codepoint's continuation bytes may only
+    /// show up in a later `advance` call.
+    fn print_byte(&mut self, grid: &mut Grid, byte: u8) {
+        if byte < 0x20 {
+            return;
+        }
+        if self.utf8_buf.is_empty() {
+            self.utf8_pending = utf8_seq_len(byte);
+        }
+        self.utf8_buf.push(byte);
+        if self.utf8_buf.len() < self.utf8_pending {
+            return;
+        }
+        let ch = std::str::from_utf8(&self.utf8_buf)
+            .ok()
+            .and_then(|s| s.chars().next())
+            .unwrap_or(char::REPLACEMENT_CHARACTER);
+        self.utf8_buf.clear();
+        self.print_char(grid, ch);
+    }
+
+    fn print_char(&mut self, grid: &mut Grid, ch: char) {
+        if grid.cursor_col >= grid.width {
+            grid.cursor_col = 0;
+            grid.newline();
+        }
+        let row = grid.cursor_row;
+        let col = grid.cursor_col;
+        let style = self.style;
+        grid.rows[row as usize][col as usize] = Cell { ch, style };
+        grid.cursor_col += 1;
+    }
+
+    fn advance_escape(&mut self, grid: &mut Grid, byte: u8) {
+        match byte {
+            b'[' => {
+                self.params.clear();
+                self.current_param = None;
+                self.state = State::Csi;
+            }
+            // OSC, DCS, SOS, PM, APC: string sequences terminated by BEL/ST,
+            // not single-byte dispatches. Window-title OSCs and the OSC 133
+            // shell-integration markers `history::CommandTracker` relies on
+            // both land here — without this they'd leak into the grid as text.
+            b']' | b'P' | b'X' | b'^' | b'_' => self.state = State::Str,
+            // Charset designation (e.g. `ESC ( B`): consumes exactly one more byte.
+            b'(' | b')' => self.state = State::Charset,
+            _ => self.state = State::Ground,
+        }
+        let _ = grid;
+    }
+
+    fn advance_str(&mut self, byte: u8) {
+        match byte {
+            0x07 => self.state = State::Ground,
+            0x1b => self.state = State::StrEscape,
+            _ => {}
+        }
+    }
+
+    fn advance_str_escape(&mut self, byte: u8) {
+        match byte {
+            b'\\' => self.state = State::Ground,
+            // Not actually `ST` (`ESC \`) — resume swallowing the string body.
+            _ => self.state = State::Str,
+        }
+    }
+
+    fn advance_csi(&mut self, grid: &mut Grid, byte: u8) {
+        match byte {
+            b'0'..=b'9' => {
+                let d = (byte - b'0') as u16;
+                let next = self
+                    .current_param
+                    .unwrap_or(0)
+                    .saturating_mul(10)
+                    .saturating_add(d);
+                self.current_param = Some(next.min(MAX_CSI_PARAM));
+            }
+            b';' => {
+                self.params.push(self.current_param.take().unwrap_or(0));
+            }
+            _ => {
+                if let Some(p) = self.current_param.take() {
+                    self.params.push(p);
+                }
+                self.dispatch_csi(grid, byte);
+                self.params.clear();
+                self.state = State::Ground;
+            }
+        }
+    }
+
+    fn param(&self, idx: usize, default: u16) -> u16 {
+        match self.params.get(idx) {
+            Some(0) | None => default,
+            Some(&v) => v,
+        }
+    }
+
+    fn dispatch_csi(&mut self, grid: &mut Grid, final_byte: u8) {
+        match final_byte {
+            b'A' => grid.cursor_row = grid.cursor_row.saturating_sub(self.param(0, 1)),
+            b'B' => {
+                grid.cursor_row = grid
+                    .cursor_row
+                    .saturating_add(self.param(0, 1))
+                    .min(grid.height - 1);
+            }
+            b'C' => {
+                grid.cursor_col = grid
+                    .cursor_col
+                    .saturating_add(self.param(0, 1))
+                    .min(grid.width - 1);
+            }
+            b'D' => grid.cursor_col = grid.cursor_col.saturating_sub(self.param(0, 1)),
+            b'H' | b'f' => {
+                let row = self.param(0, 1).saturating_sub(1);
+                let col = self.param(1, 1).saturating_sub(1);
+                grid.cursor_row = row.min(grid.height - 1);
+                grid.cursor_col = col.min(grid.width - 1);
+            }
+            b'J' => self.erase_display(grid, self.param(0, 0)),
+            b'K' => self.erase_line(grid, self.param(0, 0)),
+            b'm' => self.apply_sgr(),
+            b'r' => {
+                let top = self.param(0, 1).saturating_sub(1);
+                let bottom = self
+                    .params
+                    .get(1)
+                    .copied()
+                    .filter(|&v| v != 0)
+                    .unwrap_or(grid.height)
+                    .saturating_sub(1);
+                grid.scroll_top = top.min(grid.height - 1);
+                grid.scroll_bottom = bottom.min(grid.height - 1);
+            }
+            _ => {}
+        }
+        grid.clamp_cursor();
+    }
+
+    fn erase_display(&self, grid: &mut Grid, mode: u16) {
+        match mode {
+            0 => {
+                self.erase_line(grid, 0);
+                for r in (grid.cursor_row + 1)..grid.height {
+                    grid.rows[r as usize] = vec![Cell::default(); grid.width as usize];
+                }
+            }
+            1 => {
+                self.erase_line(grid, 1);
+                for r in 0..grid.cursor_row {
+                    grid.rows[r as usize] = vec![Cell::default(); grid.width as usize];
+                }
+            }
+            2 | 3 => {
+                for r in 0..grid.height {
+                    grid.rows[r as usize] = vec![Cell::default(); grid.width as usize];
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn erase_line(&self, grid: &mut Grid, mode: u16) {
+        let row = grid.cursor_row as usize;
+        let col = grid.cursor_col as usize;
+        match mode {
+            0 => {
+                for cell in &mut grid.rows[row][col..] {
+                    *cell = Cell::default();
+                }
+            }
+            1 => {
+                for cell in &mut grid.rows[row][..=col.min(grid.rows[row].len() - 1)] {
+                    *cell = Cell::default();
+                }
+            }
+            2 => {
+                grid.rows[row] = vec![Cell::default(); grid.width as usize];
+            }
+            _ => {}
+        }
+    }
+
+    fn apply_sgr(&mut self) {
+        if self.params.is_empty() {
+            self.style = CellStyle::default();
+            return;
+        }
+        let mut i = 0;
+        while i < self.params.len() {
+            match self.params[i] {
+                0 => self.style = CellStyle::default(),
+                1 => self.style.bold = true,
+                7 => self.style.reversed = true,
+                22 => self.style.bold = false,
+                27 => self.style.reversed = false,
+                39 => self.style.fg = None,
+                49 => self.style.bg = None,
+                code @ (30..=37 | 90..=97) => self.style.fg = color_from_sgr(code),
+                code @ (40..=47 | 100..=107) => self.style.bg = color_from_sgr(code),
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The number of bytes a UTF-8 codepoint starting with `first_byte` occupies.
+fn utf8_seq_len(first_byte: u8) -> usize {
+    if first_byte & 0x80 == 0 {
+        1
+    } else if first_byte & 0xE0 == 0xC0 {
+        2
+    } else if first_byte & 0xF0 == 0xE0 {
+        3
+    } else if first_byte & 0xF8 == 0xF0 {
+        4
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_movement_sequences() {
+        let mut grid = Grid::new(10, 5);
+        let mut parser = Parser::new();
+        parser.advance(&mut grid, b"\x1b[3;4H");
+        assert_eq!((grid.cursor_row, grid.cursor_col), (2, 3));
+        parser.advance(&mut grid, b"\x1b[2B");
+        assert_eq!(grid.cursor_row, 4);
+        parser.advance(&mut grid, b"\x1b[1C");
+        assert_eq!(grid.cursor_col, 4);
+        parser.advance(&mut grid, b"\x1b[2D");
+        assert_eq!(grid.cursor_col, 2);
+    }
+
+    #[test]
+    fn erase_display_clears_cells() {
+        let mut grid = Grid::new(5, 2);
+        let mut parser = Parser::new();
+        parser.advance(&mut grid, b"hello");
+        parser.advance(&mut grid, b"\x1b[H\x1b[2J");
+        assert_eq!(grid.row(0)[0].ch, ' ');
+    }
+
+    #[test]
+    fn sgr_applies_bold_and_color() {
+        let mut grid = Grid::new(5, 1);
+        let mut parser = Parser::new();
+        parser.advance(&mut grid, b"\x1b[1;31mx");
+        let cell = grid.row(0)[0];
+        assert!(cell.style.bold);
+        assert_eq!(cell.style.fg, Some(NamedColor::Red));
+    }
+
+    #[test]
+    fn sgr_reset_clears_style() {
+        let mut grid = Grid::new(5, 1);
+        let mut parser = Parser::new();
+        parser.advance(&mut grid, b"\x1b[1;31m\x1b[0mx");
+        let cell = grid.row(0)[0];
+        assert!(!cell.style.bold);
+        assert_eq!(cell.style.fg, None);
+    }
+
+    #[test]
+    fn osc_window_title_is_swallowed_not_printed() {
+        let mut grid = Grid::new(20, 1);
+        let mut parser = Parser::new();
+        parser.advance(&mut grid, b"\x1b]0;my title\x07ok");
+        assert_eq!(grid.row(0)[0].ch, 'o');
+        assert_eq!(grid.row(0)[1].ch, 'k');
+    }
+
+    #[test]
+    fn osc_133_shell_integration_is_swallowed() {
+        let mut grid = Grid::new(20, 1);
+        let mut parser = Parser::new();
+        parser.advance(&mut grid, b"\x1b]133;A\x1b\\ok");
+        assert_eq!(grid.row(0)[0].ch, 'o');
+        assert_eq!(grid.row(0)[1].ch, 'k');
+    }
+
+    #[test]
+    fn utf8_multibyte_char_decodes_to_one_cell() {
+        let mut grid = Grid::new(5, 1);
+        let mut parser = Parser::new();
+        parser.advance(&mut grid, "é".as_bytes());
+        assert_eq!(grid.row(0)[0].ch, 'é');
+        assert_eq!(grid.cursor_col, 1);
+    }
+
+    #[test]
+    fn long_digit_run_does_not_overflow_param_or_cursor_math() {
+        let mut grid = Grid::new(10, 5);
+        let mut parser = Parser::new();
+        parser.advance(&mut grid, b"\x1b[999999999B");
+        assert_eq!(grid.cursor_row, grid.height - 1);
+        parser.advance(&mut grid, b"\x1b[999999999C");
+        assert_eq!(grid.cursor_col, grid.width - 1);
+    }
+
+    #[test]
+    fn utf8_multibyte_char_split_across_advance_calls() {
+        let mut grid = Grid::new(5, 1);
+        let mut parser = Parser::new();
+        let bytes = "é".as_bytes();
+        parser.advance(&mut grid, &bytes[..1]);
+        parser.advance(&mut grid, &bytes[1..]);
+        assert_eq!(grid.row(0)[0].ch, 'é');
+    }
+}
@@ -0,0 +1,65 @@
+//! RAII setup/teardown for the alternate screen, so a panic anywhere in the
+//! app (the VT parser, a pane thread, anywhere) can't leave the user's
+//! terminal stuck in raw mode with a broken display.
+
+use crate::Result;
+use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::backend::CrosstermBackend;
+use std::io;
+
+/// The concrete `ratatui::Terminal` this crate renders to.
+pub type DefaultTerminal = ratatui::Terminal<CrosstermBackend<io::Stdout>>;
+
+/// Enables raw mode, enters the alternate screen, installs a panic hook that
+/// restores the terminal before printing the original panic message, and
+/// returns a ready-to-draw `DefaultTerminal` plus a guard that restores the
+/// terminal again when it drops (covering the normal-return path).
+pub fn init() -> Result<(DefaultTerminal, TerminalGuard)> {
+    let guard = TerminalGuard::new()?;
+    let backend = CrosstermBackend::new(io::stdout());
+    let terminal = ratatui::Terminal::new(backend)?;
+    Ok((terminal, guard))
+}
+
+/// Disables raw mode, leaves the alternate screen, and disables mouse
+/// capture. Safe to call more than once (e.g. once from the panic hook and
+/// once from `TerminalGuard::drop`) — a second call just errors quietly.
+pub fn restore() -> Result<()> {
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+    Ok(())
+}
+
+/// Drops to `restore()` the terminal. Hold one for the lifetime of the app
+/// (e.g. as a `main`-local binding) so the screen is restored however
+/// `main` exits.
+pub struct TerminalGuard {
+    _private: (),
+}
+
+impl TerminalGuard {
+    fn new() -> Result<Self> {
+        enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+        install_panic_hook();
+        Ok(Self { _private: () })
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = restore();
+    }
+}
+
+fn install_panic_hook() {
+    let original_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |panic_info| {
+        let _ = restore();
+        original_hook(panic_info);
+    }));
+}
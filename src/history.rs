@@ -0,0 +1,271 @@
+//! Per-pane command history, recorded from OSC 133 shell-integration markers
+//! (`\e]133;A\a` prompt start, `;B` command start, `;C` output start, `;D`
+//! command finished) rather than by guessing at prompt text. Shells like
+//! bash, zsh, and fish all support emitting these when configured for shell
+//! integration (e.g. iTerm2/VS Code style `precmd`/`preexec` hooks).
+
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    pub command: String,
+    pub duration: Duration,
+    pub exit_code: i32,
+    pub output: String,
+}
+
+impl HistoryEntry {
+    pub fn succeeded(&self) -> bool {
+        self.exit_code == 0
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum State {
+    Idle,
+    Command,
+    Output,
+}
+
+/// Scans a raw PTY byte stream for OSC 133 markers and assembles completed
+/// commands into `entries`. Feed it the exact same bytes given to the VT
+/// grid parser.
+pub struct CommandTracker {
+    state: State,
+    command_buf: String,
+    output_buf: String,
+    start: Option<Instant>,
+    /// Body bytes of an OSC sequence seen so far, when its terminator
+    /// (BEL/ST) hadn't arrived by the end of the last `feed()` call. PTY
+    /// reads are chunked arbitrarily, so a marker can legitimately be split
+    /// across reads.
+    pending_osc: Option<Vec<u8>>,
+    /// Bytes of a multi-byte UTF-8 sequence seen so far in `command_buf`,
+    /// buffered across `feed()` calls in case a codepoint is split across
+    /// PTY reads — mirrors `grid::Parser`'s decoder.
+    command_utf8_buf: Vec<u8>,
+    output_utf8_buf: Vec<u8>,
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl CommandTracker {
+    pub fn new() -> Self {
+        Self {
+            state: State::Idle,
+            command_buf: String::new(),
+            output_buf: String::new(),
+            start: None,
+            pending_osc: None,
+            command_utf8_buf: Vec::new(),
+            output_utf8_buf: Vec::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn feed(&mut self, bytes: &[u8]) {
+        let mut i = 0;
+
+        if let Some(mut pending) = self.pending_osc.take() {
+            match find_osc_end(bytes) {
+                Some((osc, consumed)) => {
+                    pending.extend_from_slice(osc);
+                    self.handle_osc(&pending);
+                    i = consumed;
+                }
+                None => {
+                    pending.extend_from_slice(bytes);
+                    self.pending_osc = Some(pending);
+                    return;
+                }
+            }
+        }
+
+        while i < bytes.len() {
+            if bytes[i] == 0x1b && bytes.get(i + 1) == Some(&b']') {
+                match find_osc_end(&bytes[i + 2..]) {
+                    Some((osc, consumed)) => {
+                        self.handle_osc(osc);
+                        i += 2 + consumed;
+                        continue;
+                    }
+                    None => {
+                        self.pending_osc = Some(bytes[i + 2..].to_vec());
+                        break;
+                    }
+                }
+            }
+
+            match self.state {
+                State::Command => {
+                    push_utf8_byte(&mut self.command_utf8_buf, &mut self.command_buf, bytes[i])
+                }
+                State::Output => {
+                    push_utf8_byte(&mut self.output_utf8_buf, &mut self.output_buf, bytes[i])
+                }
+                State::Idle => {}
+            }
+            i += 1;
+        }
+    }
+
+    fn handle_osc(&mut self, osc: &[u8]) {
+        let text = String::from_utf8_lossy(osc);
+        let mut parts = text.splitn(3, ';');
+        if parts.next() != Some("133") {
+            return;
+        }
+        match parts.next() {
+            Some("A") => self.state = State::Idle,
+            Some("B") => {
+                self.state = State::Command;
+                self.command_buf.clear();
+                self.command_utf8_buf.clear();
+                self.start = Some(Instant::now());
+            }
+            Some("C") => {
+                self.state = State::Output;
+                self.output_buf.clear();
+                self.output_utf8_buf.clear();
+            }
+            Some("D") => {
+                let exit_code = parts.next().and_then(|v| v.parse().ok()).unwrap_or(0);
+                let duration = self.start.map(|s| s.elapsed()).unwrap_or_default();
+                self.entries.push(HistoryEntry {
+                    command: self.command_buf.trim().to_string(),
+                    duration,
+                    exit_code,
+                    output: self.output_buf.clone(),
+                });
+                self.state = State::Idle;
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Default for CommandTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Buffers `byte` as part of a (possibly multi-byte) UTF-8 codepoint,
+/// decoding and appending it to `target` once the full sequence has arrived.
+/// A malformed sequence is replaced with `U+FFFD` rather than corrupting the
+/// rest of the string.
+fn push_utf8_byte(pending: &mut Vec<u8>, target: &mut String, byte: u8) {
+    pending.push(byte);
+    let expected = utf8_seq_len(pending[0]);
+    if pending.len() < expected {
+        return;
+    }
+    match std::str::from_utf8(pending) {
+        Ok(s) => target.push_str(s),
+        Err(_) => target.push(char::REPLACEMENT_CHARACTER),
+    }
+    pending.clear();
+}
+
+/// The number of bytes a UTF-8 codepoint starting with `first_byte` occupies.
+fn utf8_seq_len(first_byte: u8) -> usize {
+    if first_byte & 0x80 == 0 {
+        1
+    } else if first_byte & 0xE0 == 0xC0 {
+        2
+    } else if first_byte & 0xF0 == 0xE0 {
+        3
+    } else if first_byte & 0xF8 == 0xF0 {
+        4
+    } else {
+        1
+    }
+}
+
+/// Finds the terminator (BEL or ST) of an OSC sequence starting right after
+/// `\e]`, returning the sequence body and how many bytes (body + terminator)
+/// were consumed.
+fn find_osc_end(rest: &[u8]) -> Option<(&[u8], usize)> {
+    let mut j = 0;
+    while j < rest.len() {
+        if rest[j] == 0x07 {
+            return Some((&rest[..j], j + 1));
+        }
+        if rest[j] == 0x1b && rest.get(j + 1) == Some(&b'\\') {
+            return Some((&rest[..j], j + 2));
+        }
+        j += 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_a_completed_command() {
+        let mut tracker = CommandTracker::new();
+        tracker.feed(b"\x1b]133;A\x07");
+        tracker.feed(b"$ ");
+        tracker.feed(b"\x1b]133;B\x07");
+        tracker.feed(b"echo hi");
+        tracker.feed(b"\x1b]133;C\x07");
+        tracker.feed(b"hi\n");
+        tracker.feed(b"\x1b]133;D;0\x07");
+
+        assert_eq!(tracker.entries.len(), 1);
+        let entry = &tracker.entries[0];
+        assert_eq!(entry.command, "echo hi");
+        assert_eq!(entry.output, "hi\n");
+        assert!(entry.succeeded());
+    }
+
+    #[test]
+    fn records_a_nonzero_exit_code() {
+        let mut tracker = CommandTracker::new();
+        tracker.feed(b"\x1b]133;B\x07false\x1b]133;C\x07\x1b]133;D;1\x07");
+        assert_eq!(tracker.entries[0].exit_code, 1);
+        assert!(!tracker.entries[0].succeeded());
+    }
+
+    #[test]
+    fn command_text_accumulates_across_feed_calls() {
+        let mut tracker = CommandTracker::new();
+        tracker.feed(b"\x1b]133;B\x07ec");
+        tracker.feed(b"ho hi\x1b]133;C\x07hi\n\x1b]133;D;0\x07");
+        assert_eq!(tracker.entries[0].command, "echo hi");
+    }
+
+    #[test]
+    fn decodes_utf8_in_command_and_output() {
+        let mut tracker = CommandTracker::new();
+        tracker.feed("\x1b]133;B\x07echo café".as_bytes());
+        tracker.feed("\x1b]133;C\x07café\n".as_bytes());
+        tracker.feed(b"\x1b]133;D;0\x07");
+        assert_eq!(tracker.entries[0].command, "echo café");
+        assert_eq!(tracker.entries[0].output, "café\n");
+    }
+
+    #[test]
+    fn decodes_utf8_char_split_across_feed_calls() {
+        let mut tracker = CommandTracker::new();
+        let bytes = "echo café".as_bytes();
+        let (head, tail) = bytes.split_at(bytes.len() - 1);
+        tracker.feed(b"\x1b]133;B\x07");
+        tracker.feed(head);
+        tracker.feed(tail);
+        tracker.feed(b"\x1b]133;C\x07\x1b]133;D;0\x07");
+        assert_eq!(tracker.entries[0].command, "echo café");
+    }
+
+    #[test]
+    fn handles_osc_terminator_split_across_feed_calls() {
+        let mut tracker = CommandTracker::new();
+        tracker.feed(b"\x1b]133;B\x07echo hi\x1b]133;C\x07hi\n\x1b]133;D;0");
+        assert!(tracker.entries.is_empty(), "entry shouldn't land until the terminator arrives");
+        tracker.feed(b"\x07");
+        assert_eq!(tracker.entries.len(), 1);
+        assert_eq!(tracker.entries[0].command, "echo hi");
+        assert_eq!(tracker.entries[0].exit_code, 0);
+    }
+}
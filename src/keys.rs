@@ -0,0 +1,126 @@
+//! Maps crossterm key events onto the byte sequences a PTY-backed terminal
+//! expects on its input stream (UTF-8 for printable chars, control codes for
+//! Ctrl-modified letters, and the usual xterm escape sequences for the
+//! cursor/navigation keys).
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Encodes a key event as the bytes that should be written to the focused
+/// pane's PTY, or `None` if the key has no terminal-input meaning (e.g. a
+/// bare modifier).
+pub fn encode_key(key: KeyEvent) -> Option<Vec<u8>> {
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        if let KeyCode::Char(c) = key.code {
+            if c.is_ascii_alphabetic() {
+                // Ctrl-<letter> maps to its control code: Ctrl-A = 0x01 ... Ctrl-Z = 0x1a.
+                let code = (c.to_ascii_uppercase() as u8) & 0x1f;
+                return Some(vec![code]);
+            }
+        }
+    }
+
+    match key.code {
+        KeyCode::Char(c) => Some(c.to_string().into_bytes()),
+        KeyCode::Enter => Some(b"\r".to_vec()),
+        KeyCode::Backspace => Some(vec![0x7f]),
+        KeyCode::Tab => Some(b"\t".to_vec()),
+        KeyCode::BackTab => Some(b"\x1b[Z".to_vec()),
+        KeyCode::Esc => Some(vec![0x1b]),
+        KeyCode::Up => Some(b"\x1b[A".to_vec()),
+        KeyCode::Down => Some(b"\x1b[B".to_vec()),
+        KeyCode::Right => Some(b"\x1b[C".to_vec()),
+        KeyCode::Left => Some(b"\x1b[D".to_vec()),
+        KeyCode::Home => Some(b"\x1b[H".to_vec()),
+        KeyCode::End => Some(b"\x1b[F".to_vec()),
+        KeyCode::PageUp => Some(b"\x1b[5~".to_vec()),
+        KeyCode::PageDown => Some(b"\x1b[6~".to_vec()),
+        KeyCode::Delete => Some(b"\x1b[3~".to_vec()),
+        KeyCode::Insert => Some(b"\x1b[2~".to_vec()),
+        KeyCode::F(n) => Some(encode_function_key(n)).filter(|bytes| !bytes.is_empty()),
+        _ => None,
+    }
+}
+
+fn encode_function_key(n: u8) -> Vec<u8> {
+    match n {
+        1 => b"\x1bOP".to_vec(),
+        2 => b"\x1bOQ".to_vec(),
+        3 => b"\x1bOR".to_vec(),
+        4 => b"\x1bOS".to_vec(),
+        5 => b"\x1b[15~".to_vec(),
+        6 => b"\x1b[17~".to_vec(),
+        7 => b"\x1b[18~".to_vec(),
+        8 => b"\x1b[19~".to_vec(),
+        9 => b"\x1b[20~".to_vec(),
+        10 => b"\x1b[21~".to_vec(),
+        11 => b"\x1b[23~".to_vec(),
+        12 => b"\x1b[24~".to_vec(),
+        _ => Vec::new(),
+    }
+}
+
+/// The key combination that toggles between pass-through (pane gets raw
+/// input) and command mode (the app's own key bindings). Ctrl-B matches
+/// tmux's default leader, which is the mental model this most resembles.
+pub fn is_leader(key: KeyEvent) -> bool {
+    key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('b')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyEventKind, KeyEventState};
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }
+    }
+
+    #[test]
+    fn ctrl_c_maps_to_etx() {
+        let bytes = encode_key(key(KeyCode::Char('c'), KeyModifiers::CONTROL)).unwrap();
+        assert_eq!(bytes, vec![0x03]);
+    }
+
+    #[test]
+    fn ctrl_d_maps_to_eot() {
+        let bytes = encode_key(key(KeyCode::Char('d'), KeyModifiers::CONTROL)).unwrap();
+        assert_eq!(bytes, vec![0x04]);
+    }
+
+    #[test]
+    fn printable_char_is_utf8() {
+        let bytes = encode_key(key(KeyCode::Char('a'), KeyModifiers::NONE)).unwrap();
+        assert_eq!(bytes, b"a".to_vec());
+    }
+
+    #[test]
+    fn arrow_keys_use_xterm_sequences() {
+        assert_eq!(
+            encode_key(key(KeyCode::Up, KeyModifiers::NONE)).unwrap(),
+            b"\x1b[A".to_vec()
+        );
+        assert_eq!(
+            encode_key(key(KeyCode::Home, KeyModifiers::NONE)).unwrap(),
+            b"\x1b[H".to_vec()
+        );
+    }
+
+    #[test]
+    fn f1_uses_ss3_sequence() {
+        assert_eq!(
+            encode_key(key(KeyCode::F(1), KeyModifiers::NONE)).unwrap(),
+            b"\x1bOP".to_vec()
+        );
+    }
+
+    #[test]
+    fn leader_is_ctrl_b() {
+        assert!(is_leader(key(KeyCode::Char('b'), KeyModifiers::CONTROL)));
+        assert!(!is_leader(key(KeyCode::Char('b'), KeyModifiers::NONE)));
+    }
+}
@@ -0,0 +1,170 @@
+//! A binary tree of panes, generalizing the old fixed two-pane 50/50 split
+//! into an arbitrarily deep tmux-style layout: any leaf can be split again,
+//! in either direction, at any ratio.
+
+use ratatui::layout::{Constraint, Direction, Layout as RatatuiLayout, Rect};
+
+pub type PaneId = usize;
+
+/// The minimum/maximum percentage either side of a split may shrink to.
+const MIN_RATIO: i16 = 10;
+const MAX_RATIO: i16 = 90;
+
+#[derive(Debug, Clone)]
+pub enum Layout {
+    Leaf(PaneId),
+    Split {
+        dir: Direction,
+        ratio: u16,
+        first: Box<Layout>,
+        second: Box<Layout>,
+    },
+}
+
+impl Layout {
+    /// Leaves in left-to-right / top-to-bottom order — the order `Tab`
+    /// cycles focus through.
+    pub fn leaves(&self) -> Vec<PaneId> {
+        match self {
+            Layout::Leaf(id) => vec![*id],
+            Layout::Split { first, second, .. } => {
+                let mut ids = first.leaves();
+                ids.extend(second.leaves());
+                ids
+            }
+        }
+    }
+
+    /// Splits `target`'s leaf into a new `Split`, with `target` as `first`
+    /// and `new_id` as `second`. Returns `false` if `target` isn't in the
+    /// tree.
+    pub fn split(&mut self, target: PaneId, new_id: PaneId, dir: Direction) -> bool {
+        match self {
+            Layout::Leaf(id) if *id == target => {
+                *self = Layout::Split {
+                    dir,
+                    ratio: 50,
+                    first: Box::new(Layout::Leaf(target)),
+                    second: Box::new(Layout::Leaf(new_id)),
+                };
+                true
+            }
+            Layout::Leaf(_) => false,
+            Layout::Split { first, second, .. } => {
+                first.split(target, new_id, dir) || second.split(target, new_id, dir)
+            }
+        }
+    }
+
+    /// Removes `target`'s leaf, collapsing its parent `Split` up into
+    /// `target`'s sibling. Returns `false` if `target` is the tree's only
+    /// leaf (the last pane can't be closed) or wasn't found.
+    pub fn close(&mut self, target: PaneId) -> bool {
+        match self {
+            Layout::Leaf(_) => false,
+            Layout::Split { first, second, .. } => {
+                if matches!(**first, Layout::Leaf(id) if id == target) {
+                    *self = (**second).clone();
+                    return true;
+                }
+                if matches!(**second, Layout::Leaf(id) if id == target) {
+                    *self = (**first).clone();
+                    return true;
+                }
+                first.close(target) || second.close(target)
+            }
+        }
+    }
+
+    /// Adjusts the ratio of `target`'s innermost ancestor `Split` by `delta`
+    /// percentage points, clamped to `[MIN_RATIO, MAX_RATIO]`.
+    pub fn adjust_ratio(&mut self, target: PaneId, delta: i16) -> bool {
+        match self {
+            Layout::Leaf(_) => false,
+            Layout::Split {
+                ratio, first, second, ..
+            } => {
+                if first.adjust_ratio(target, delta) || second.adjust_ratio(target, delta) {
+                    return true;
+                }
+                let is_direct_child = matches!(**first, Layout::Leaf(id) if id == target)
+                    || matches!(**second, Layout::Leaf(id) if id == target);
+                if is_direct_child {
+                    *ratio = (*ratio as i16 + delta).clamp(MIN_RATIO, MAX_RATIO) as u16;
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    /// Recursively applies `ratatui::layout::Layout` down the tree,
+    /// collecting the screen rect each leaf should render into.
+    pub fn compute_rects(&self, area: Rect, out: &mut Vec<(PaneId, Rect)>) {
+        match self {
+            Layout::Leaf(id) => out.push((*id, area)),
+            Layout::Split {
+                dir,
+                ratio,
+                first,
+                second,
+            } => {
+                let chunks = RatatuiLayout::default()
+                    .direction(*dir)
+                    .constraints([
+                        Constraint::Percentage(*ratio),
+                        Constraint::Percentage(100 - *ratio),
+                    ])
+                    .split(area);
+                first.compute_rects(chunks[0], out);
+                second.compute_rects(chunks[1], out);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaves_in_order() {
+        let mut layout = Layout::Leaf(0);
+        layout.split(0, 1, Direction::Vertical);
+        assert_eq!(layout.leaves(), vec![0, 1]);
+    }
+
+    #[test]
+    fn split_then_close_restores_single_leaf() {
+        let mut layout = Layout::Leaf(0);
+        layout.split(0, 1, Direction::Horizontal);
+        assert!(layout.close(1));
+        assert_eq!(layout.leaves(), vec![0]);
+    }
+
+    #[test]
+    fn close_last_leaf_is_noop() {
+        let mut layout = Layout::Leaf(0);
+        assert!(!layout.close(0));
+        assert_eq!(layout.leaves(), vec![0]);
+    }
+
+    #[test]
+    fn adjust_ratio_targets_innermost_split() {
+        let mut layout = Layout::Leaf(0);
+        layout.split(0, 1, Direction::Vertical);
+        layout.split(1, 2, Direction::Horizontal);
+        layout.adjust_ratio(2, 20);
+        match &layout {
+            Layout::Split { ratio, second, .. } => {
+                assert_eq!(*ratio, 50, "outer split should be untouched");
+                match second.as_ref() {
+                    Layout::Split { ratio, .. } => assert_eq!(*ratio, 70),
+                    _ => panic!("expected nested split"),
+                }
+            }
+            _ => panic!("expected split"),
+        }
+    }
+}
@@ -2,6 +2,14 @@ use anyhow::Result;
 use portable_pty::{native_pty_system, CommandBuilder, PtySize, MasterPty, Child};
 use std::io::{Read, Write};
 
+pub mod grid;
+pub mod guard;
+pub mod history;
+pub mod pty_config;
+
+pub use guard::{init, restore, DefaultTerminal, TerminalGuard};
+pub use pty_config::PtyConfig;
+
 pub struct Terminal {
     master: Box<dyn MasterPty + Send>,
     child: Box<dyn Child + Send + Sync>,
@@ -16,28 +24,46 @@ impl Drop for Terminal {
 
 impl Terminal {
     pub fn new() -> Result<Self> {
-        Self::new_with_size(24, 80)
+        Self::spawn(PtyConfig::default())
     }
-    
+
     pub fn new_with_size(rows: u16, cols: u16) -> Result<Self> {
-        let pty_system = native_pty_system();
-        
-        let pty_pair = pty_system.openpty(PtySize {
+        Self::spawn(PtyConfig {
             rows,
             cols,
+            ..PtyConfig::default()
+        })
+    }
+
+    /// Spawns `cfg.program` (defaulting to the user's shell) behind a PTY of
+    /// `cfg.rows` x `cfg.cols`, in `cfg.cwd` if given, with `cfg.env`
+    /// applied on top of the inherited environment.
+    pub fn spawn(cfg: PtyConfig) -> Result<Self> {
+        let pty_system = native_pty_system();
+
+        let pty_pair = pty_system.openpty(PtySize {
+            rows: cfg.rows,
+            cols: cfg.cols,
             pixel_width: 0,
             pixel_height: 0,
         })?;
-        
-        let cmd = CommandBuilder::new("zsh");
+
+        let mut cmd = CommandBuilder::new(&cfg.program);
+        cmd.args(&cfg.args);
+        if let Some(cwd) = &cfg.cwd {
+            cmd.cwd(cwd);
+        }
+        for (key, value) in &cfg.env {
+            cmd.env(key, value);
+        }
         let child = pty_pair.slave.spawn_command(cmd)?;
-        
+
         Ok(Terminal {
             master: pty_pair.master,
             child,
         })
     }
-    
+
     pub fn resize(&self, rows: u16, cols: u16) -> Result<()> {
         self.master.resize(PtySize {
             rows,
@@ -57,6 +83,20 @@ impl Terminal {
         let mut reader = self.master.try_clone_reader()?;
         Ok(reader.read(buf)?)
     }
+
+    /// Clones a standalone reader over the PTY master, independent of
+    /// `read`'s per-call clone. Intended for a dedicated background thread
+    /// that continuously drains output without contending with writers.
+    pub fn clone_reader(&self) -> Result<Box<dyn Read + Send>> {
+        Ok(Box::new(self.master.try_clone_reader()?))
+    }
+
+    /// Non-blocking check for whether the child has exited, returning its
+    /// exit code if so. Used by the main loop's ticker to notice a pane's
+    /// shell quitting without a dedicated waiter thread per pane.
+    pub fn try_wait(&mut self) -> Result<Option<u32>> {
+        Ok(self.child.try_wait()?.map(|status| status.exit_code()))
+    }
 }
 
 #[cfg(test)]
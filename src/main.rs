@@ -1,132 +1,233 @@
-use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
+use crossterm::event::{KeyCode, KeyModifiers};
 use ratatui::{
-    backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout},
+    layout::{Direction, Rect},
     style::{Color, Style},
-    text::Line,
-    widgets::{Block, Borders, Paragraph, Wrap},
-    Frame, Terminal,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame, Terminal as RatatuiTerminal,
 };
 use std::{
-    error::Error, 
-    io,
-    process::Command,
-    time::{Duration, Instant},
+    collections::HashMap,
+    io::{self, Read},
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::Duration,
+};
+use tui_split::{
+    grid::{Cell, Grid, Parser},
+    history::CommandTracker,
+    PtyConfig, Terminal as PtyTerminal,
 };
 
+mod events;
+mod keys;
+mod layout;
+
+use events::Event;
+use layout::{Layout as PaneLayout, PaneId};
+
+const TICK_RATE: Duration = Duration::from_millis(250);
+const RATIO_STEP: i16 = 5;
+
 struct Pane {
     title: String,
-    command: String,
-    output: String,
-    last_update: Instant,
+    terminal: PtyTerminal,
+    grid: Arc<Mutex<Grid>>,
+    history: Arc<Mutex<CommandTracker>>,
     scroll_offset: u16,
+    size: (u16, u16),
+    exit_code: Option<u32>,
 }
 
 impl Pane {
-    fn new(title: &str, command: &str) -> Self {
-        Self {
+    fn new(id: PaneId, title: &str, cfg: PtyConfig, tx: mpsc::Sender<Event>) -> anyhow::Result<Self> {
+        let (cols, rows) = (cfg.cols, cfg.rows);
+        let terminal = PtyTerminal::spawn(cfg)?;
+
+        let grid = Arc::new(Mutex::new(Grid::new(cols, rows)));
+        let history = Arc::new(Mutex::new(CommandTracker::new()));
+        let reader = terminal.clone_reader()?;
+        let grid_for_reader = Arc::clone(&grid);
+        let history_for_reader = Arc::clone(&history);
+        thread::spawn(move || read_into_grid(id, reader, grid_for_reader, history_for_reader, tx));
+
+        Ok(Self {
             title: title.to_string(),
-            command: command.to_string(),
-            output: String::new(),
-            last_update: Instant::now() - Duration::from_secs(60), // Force initial update
+            terminal,
+            grid,
+            history,
             scroll_offset: 0,
-        }
+            size: (cols, rows),
+            exit_code: None,
+        })
     }
 
-    fn update(&mut self) {
-        // Update every 2 seconds
-        if self.last_update.elapsed() > Duration::from_secs(2) {
-            match Command::new("sh")
-                .arg("-c")
-                .arg(&self.command)
-                .output()
-            {
-                Ok(output) => {
-                    self.output = String::from_utf8_lossy(&output.stdout).to_string();
-                    if !output.stderr.is_empty() {
-                        self.output.push_str("\n--- STDERR ---\n");
-                        self.output.push_str(&String::from_utf8_lossy(&output.stderr));
-                    }
-                }
-                Err(e) => {
-                    self.output = format!("Error executing command: {}", e);
-                }
-            }
-            self.last_update = Instant::now();
+    fn resize(&mut self, cols: u16, rows: u16) {
+        if self.size == (cols, rows) {
+            return;
         }
+        let _ = self.terminal.resize(rows, cols);
+        self.grid.lock().unwrap().resize(cols, rows);
+        self.size = (cols, rows);
     }
 
     fn scroll_up(&mut self) {
-        if self.scroll_offset > 0 {
-            self.scroll_offset -= 1;
-        }
+        self.scroll_offset = self.scroll_offset.saturating_add(1);
     }
 
     fn scroll_down(&mut self) {
-        let line_count = self.output.lines().count() as u16;
-        if self.scroll_offset < line_count.saturating_sub(1) {
-            self.scroll_offset += 1;
-        }
+        self.scroll_offset = self.scroll_offset.saturating_sub(1);
     }
+}
 
-    fn reset_scroll(&mut self) {
-        self.scroll_offset = 0;
+/// Drains PTY output on a dedicated thread, feeding it through the VT parser
+/// into the pane's shared grid and notifying the main loop via `tx` so
+/// `run_app` only redraws when there's actually something new to show.
+fn read_into_grid(
+    id: PaneId,
+    mut reader: Box<dyn io::Read + Send>,
+    grid: Arc<Mutex<Grid>>,
+    history: Arc<Mutex<CommandTracker>>,
+    tx: mpsc::Sender<Event>,
+) {
+    let mut parser = Parser::new();
+    let mut buf = [0u8; 4096];
+    loop {
+        match reader.read(&mut buf) {
+            Ok(0) => return,
+            Ok(n) => {
+                {
+                    let mut grid = grid.lock().unwrap();
+                    parser.advance(&mut grid, &buf[..n]);
+                }
+                history.lock().unwrap().feed(&buf[..n]);
+                if tx.send(Event::PtyOutput(id)).is_err() {
+                    return;
+                }
+            }
+            Err(_) => return,
+        }
     }
 }
 
+/// Whether key events are forwarded to the focused pane's PTY or handled as
+/// app commands (split/focus/quit). The leader key (`keys::is_leader`)
+/// toggles between the two, mirroring tmux's prefix-key model.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum InputMode {
+    Command,
+    PassThrough,
+}
+
+/// Whether the history overlay for the focused pane is open, and which
+/// entry is selected.
+#[derive(Default)]
+struct HistoryOverlay {
+    open: bool,
+    selected: usize,
+}
+
 struct App {
-    panes: Vec<Pane>,
-    split_horizontal: bool,
-    focused_pane: usize,
+    panes: HashMap<PaneId, Pane>,
+    layout: PaneLayout,
+    focused: PaneId,
+    input_mode: InputMode,
+    tx: mpsc::Sender<Event>,
+    next_id: PaneId,
+    history_overlay: HistoryOverlay,
 }
 
 impl App {
-    fn new() -> Self {
-        Self {
-            panes: vec![
-                Pane::new("System Info", "date && echo && uname -a && echo && uptime"),
-                Pane::new("Process List", "ps aux | head -20"),
-            ],
-            split_horizontal: false,
-            focused_pane: 0,
+    fn new(tx: mpsc::Sender<Event>) -> anyhow::Result<Self> {
+        let mut panes = HashMap::new();
+        panes.insert(
+            0,
+            Pane::new(
+                0,
+                "System Info",
+                PtyConfig::shell_command("date && echo && uname -a && echo && uptime"),
+                tx.clone(),
+            )?,
+        );
+        panes.insert(
+            1,
+            Pane::new(
+                1,
+                "Process List",
+                PtyConfig::shell_command("ps aux | head -20"),
+                tx.clone(),
+            )?,
+        );
+
+        Ok(Self {
+            panes,
+            layout: PaneLayout::Split {
+                dir: Direction::Vertical,
+                ratio: 50,
+                first: Box::new(PaneLayout::Leaf(0)),
+                second: Box::new(PaneLayout::Leaf(1)),
+            },
+            focused: 0,
+            input_mode: InputMode::PassThrough,
+            tx,
+            next_id: 2,
+            history_overlay: HistoryOverlay::default(),
+        })
+    }
+
+    fn switch_focus(&mut self) {
+        let leaves = self.layout.leaves();
+        if let Some(pos) = leaves.iter().position(|&id| id == self.focused) {
+            self.focused = leaves[(pos + 1) % leaves.len()];
         }
     }
 
-    fn update(&mut self) {
-        for pane in &mut self.panes {
-            pane.update();
+    /// Splits the focused pane, spawning a fresh shell into the new half.
+    fn split_focused(&mut self, dir: Direction) {
+        let new_id = self.next_id;
+        self.next_id += 1;
+        if let Ok(pane) = Pane::new(new_id, "Shell", PtyConfig::default(), self.tx.clone()) {
+            self.layout.split(self.focused, new_id, dir);
+            self.panes.insert(new_id, pane);
+            self.focused = new_id;
         }
     }
 
-    fn switch_focus(&mut self) {
-        self.focused_pane = (self.focused_pane + 1) % self.panes.len();
+    /// Closes the focused pane, collapsing its sibling up the tree. Leaves
+    /// the layout untouched if it's the last remaining pane.
+    fn close_focused(&mut self) {
+        if self.panes.len() <= 1 {
+            return;
+        }
+        let target = self.focused;
+        if self.layout.close(target) {
+            self.panes.remove(&target);
+            self.focused = self.layout.leaves()[0];
+        }
+    }
+
+    fn adjust_focused_ratio(&mut self, delta: i16) {
+        self.layout.adjust_ratio(self.focused, delta);
     }
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    // Setup terminal
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+fn main() -> anyhow::Result<()> {
+    // `_guard` restores the terminal on drop, whether `main` returns
+    // normally or unwinds; the panic hook installed inside `init` covers
+    // the case where a panic happens before unwinding reaches here.
+    let (mut terminal, _guard) = tui_split::init()?;
 
-    // Application state
-    let mut app = App::new();
+    // Event plumbing: input + ticker threads feed this channel, and each
+    // pane's reader thread (spawned in `Pane::new`) adds its own PtyOutput.
+    let (tx, rx) = mpsc::channel();
+    events::spawn_input_thread(tx.clone());
+    events::spawn_ticker(tx.clone(), TICK_RATE);
 
-    let res = run_app(&mut terminal, &mut app);
+    // Application state
+    let mut app = App::new(tx)?;
 
-    // Cleanup
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
+    let res = run_app(&mut terminal, &mut app, rx);
+    tui_split::restore()?;
     terminal.show_cursor()?;
 
     if let Err(err) = res {
@@ -137,95 +238,353 @@ fn main() -> Result<(), Box<dyn Error>> {
 }
 
 fn run_app<B: ratatui::backend::Backend>(
-    terminal: &mut Terminal<B>,
+    terminal: &mut RatatuiTerminal<B>,
     app: &mut App,
+    rx: mpsc::Receiver<Event>,
 ) -> io::Result<()> {
     loop {
-        app.update();
-        terminal.draw(|f| ui(f, app))?;
-
-        if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                match key.code {
-                    KeyCode::Char('q') => return Ok(()),
-                    KeyCode::Char('h') => app.split_horizontal = true,
-                    KeyCode::Char('v') => app.split_horizontal = false,
-                    KeyCode::Tab => app.switch_focus(),
-                    KeyCode::Up => {
-                        app.panes[app.focused_pane].scroll_up();
-                    }
-                    KeyCode::Down => {
-                        app.panes[app.focused_pane].scroll_down();
-                    }
-                    KeyCode::Char('1') => {
-                        app.panes[0] = Pane::new("Disk Usage", "df -h");
-                    }
-                    KeyCode::Char('2') => {
-                        app.panes[1] = Pane::new("Network Info", "ifconfig");
-                    }
-                    KeyCode::Char('3') => {
-                        app.panes[0] = Pane::new("Memory Info", "free -h");
-                    }
-                    KeyCode::Char('4') => {
-                        app.panes[1] = Pane::new("CPU Info", "lscpu");
-                    }
-                    _ => {}
+        let mut needs_redraw = false;
+
+        let first = match rx.recv() {
+            Ok(ev) => ev,
+            Err(_) => return Ok(()),
+        };
+        if !handle_event(app, first, &mut needs_redraw) {
+            return Ok(());
+        }
+        // Drain whatever else is already queued so a burst of PtyOutput (or
+        // input) only triggers one redraw instead of one per message.
+        while let Ok(ev) = rx.try_recv() {
+            if !handle_event(app, ev, &mut needs_redraw) {
+                return Ok(());
+            }
+        }
+
+        if needs_redraw {
+            resize_panes_to_layout(terminal.size()?, app);
+            terminal.draw(|f| ui(f, app))?;
+        }
+    }
+}
+
+/// Dispatches one event, mutating `app` and setting `*needs_redraw` when the
+/// screen should be refreshed. Returns `false` to request app exit.
+fn handle_event(app: &mut App, ev: Event, needs_redraw: &mut bool) -> bool {
+    match ev {
+        Event::Key(key) => {
+            *needs_redraw = true;
+            return handle_key(app, key);
+        }
+        Event::Resize(_, _) => *needs_redraw = true,
+        Event::PtyOutput(_id) => *needs_redraw = true,
+        Event::ChildExit(id, code) => {
+            if let Some(pane) = app.panes.get_mut(&id) {
+                pane.exit_code = Some(code);
+            }
+            *needs_redraw = true;
+        }
+        Event::Tick => {
+            // No dedicated waiter thread per child; piggyback the exit check
+            // on the redraw ticker instead.
+            let mut exits = Vec::new();
+            for (&id, pane) in app.panes.iter_mut() {
+                if let Ok(Some(code)) = pane.terminal.try_wait() {
+                    exits.push((id, code));
+                }
+            }
+            for (id, code) in exits {
+                if !handle_event(app, Event::ChildExit(id, code), needs_redraw) {
+                    return false;
                 }
             }
+            *needs_redraw = true;
         }
     }
+    true
 }
 
-fn ui(f: &mut Frame, app: &App) {
-    // Determine split direction
-    let direction = if app.split_horizontal {
-        Direction::Horizontal
-    } else {
-        Direction::Vertical
+fn handle_key(app: &mut App, key: crossterm::event::KeyEvent) -> bool {
+    if keys::is_leader(key) {
+        app.input_mode = match app.input_mode {
+            InputMode::Command => InputMode::PassThrough,
+            InputMode::PassThrough => InputMode::Command,
+        };
+        return true;
+    }
+
+    if app.history_overlay.open {
+        return handle_history_overlay_key(app, key);
+    }
+
+    if app.input_mode == InputMode::PassThrough {
+        if let Some(bytes) = keys::encode_key(key) {
+            if let Some(pane) = app.panes.get_mut(&app.focused) {
+                let _ = pane.terminal.write(&bytes);
+            }
+        }
+        return true;
+    }
+
+    let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+    match key.code {
+        KeyCode::Char('q') => return false,
+        KeyCode::Char('h') => app.split_focused(Direction::Horizontal),
+        KeyCode::Char('v') => app.split_focused(Direction::Vertical),
+        KeyCode::Char('x') => app.close_focused(),
+        KeyCode::Char('e') => {
+            app.history_overlay.open = true;
+            app.history_overlay.selected = 0;
+        }
+        KeyCode::Tab => app.switch_focus(),
+        KeyCode::Up if shift => app.adjust_focused_ratio(-RATIO_STEP),
+        KeyCode::Down if shift => app.adjust_focused_ratio(RATIO_STEP),
+        KeyCode::Left if shift => app.adjust_focused_ratio(-RATIO_STEP),
+        KeyCode::Right if shift => app.adjust_focused_ratio(RATIO_STEP),
+        KeyCode::Up => {
+            if let Some(pane) = app.panes.get_mut(&app.focused) {
+                pane.scroll_up();
+            }
+        }
+        KeyCode::Down => {
+            if let Some(pane) = app.panes.get_mut(&app.focused) {
+                pane.scroll_down();
+            }
+        }
+        _ => {}
+    }
+    true
+}
+
+/// Handles input while the history overlay for the focused pane is open:
+/// navigate entries, re-run or copy the selected one, or close it.
+fn handle_history_overlay_key(app: &mut App, key: crossterm::event::KeyEvent) -> bool {
+    let entry_count = app
+        .panes
+        .get(&app.focused)
+        .map(|p| p.history.lock().unwrap().entries.len())
+        .unwrap_or(0);
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('e') => app.history_overlay.open = false,
+        KeyCode::Up => {
+            app.history_overlay.selected = app.history_overlay.selected.saturating_sub(1);
+        }
+        KeyCode::Down => {
+            if app.history_overlay.selected + 1 < entry_count {
+                app.history_overlay.selected += 1;
+            }
+        }
+        KeyCode::Enter => {
+            run_selected_history_entry(app, true);
+            app.history_overlay.open = false;
+        }
+        KeyCode::Char('c') => {
+            run_selected_history_entry(app, false);
+            app.history_overlay.open = false;
+        }
+        _ => {}
+    }
+    true
+}
+
+/// Writes the selected history entry's command line to the focused pane's
+/// PTY, either re-running it (`execute`) or just placing it at the prompt
+/// for the user to edit (copy).
+fn run_selected_history_entry(app: &mut App, execute: bool) {
+    let Some(pane) = app.panes.get_mut(&app.focused) else {
+        return;
     };
+    let selected = app.history_overlay.selected;
+    let command = pane
+        .history
+        .lock()
+        .unwrap()
+        .entries
+        .get(selected)
+        .map(|e| e.command.clone());
+    if let Some(command) = command {
+        let payload = if execute {
+            format!("{command}\n")
+        } else {
+            command
+        };
+        let _ = pane.terminal.write(payload.as_bytes());
+    }
+}
 
-    // Create layout (50% split)
-    let chunks = Layout::default()
-        .direction(direction)
-        .margin(1)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(f.area());
-
-    // Render each pane
-    for (i, pane) in app.panes.iter().enumerate() {
-        let is_focused = i == app.focused_pane;
-        
-        let block = Block::default()
-            .title(format!("{} | Command: {} {}", 
-                pane.title, 
-                pane.command,
+/// Keeps each pane's PTY and VT grid in sync with the size of the screen
+/// region it's actually rendered into, so full-screen programs like `vim`
+/// see the right dimensions instead of the `PtyConfig` default of 24x80.
+fn resize_panes_to_layout(area: Rect, app: &mut App) {
+    let area = margin_one(area);
+    let mut rects = Vec::new();
+    app.layout.compute_rects(area, &mut rects);
+
+    for (id, rect) in rects {
+        if let Some(pane) = app.panes.get_mut(&id) {
+            // Borders eat one row/col on every side.
+            let cols = rect.width.saturating_sub(2);
+            let rows = rect.height.saturating_sub(2);
+            if cols > 0 && rows > 0 {
+                pane.resize(cols, rows);
+            }
+        }
+    }
+}
+
+fn margin_one(area: Rect) -> Rect {
+    Rect {
+        x: area.x + 1,
+        y: area.y + 1,
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(2),
+    }
+}
+
+/// Collapses a row of cells into the minimal set of styled `Span`s, grouping
+/// consecutive cells that share a style instead of emitting one span per
+/// character.
+fn line_from_cells(cells: &[Cell]) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut run_style: Option<Style> = None;
+
+    for cell in cells {
+        let style = cell.style.to_ratatui();
+        match run_style {
+            Some(s) if s == style => run.push(cell.ch),
+            _ => {
+                if let Some(s) = run_style.take() {
+                    spans.push(Span::styled(std::mem::take(&mut run), s));
+                }
+                run.push(cell.ch);
+                run_style = Some(style);
+            }
+        }
+    }
+    if let Some(s) = run_style {
+        spans.push(Span::styled(run, s));
+    }
+
+    Line::from(spans)
+}
+
+fn ui(f: &mut Frame, app: &App) {
+    let area = margin_one(f.area());
+    let mut rects = Vec::new();
+    app.layout.compute_rects(area, &mut rects);
+
+    for (id, rect) in rects {
+        let Some(pane) = app.panes.get(&id) else {
+            continue;
+        };
+        let is_focused = id == app.focused;
+
+        let title = match pane.exit_code {
+            Some(code) => format!("{} [exited: {code}]", pane.title),
+            None => format!(
+                "{} {}",
+                pane.title,
                 if is_focused { "[FOCUSED]" } else { "" }
-            ))
+            ),
+        };
+
+        let border_color = if pane.exit_code.is_some() {
+            Color::Red
+        } else if is_focused {
+            Color::Cyan
+        } else {
+            Color::White
+        };
+
+        let block = Block::default()
+            .title(title)
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(
-                if is_focused { Color::Cyan } else { Color::White }
-            ));
-
-        let lines: Vec<Line> = pane.output
-            .lines()
-            .skip(pane.scroll_offset as usize)
-            .map(|line| Line::from(line.to_string()))
+            .border_style(Style::default().fg(border_color));
+
+        let grid = pane.grid.lock().unwrap();
+        let lines: Vec<Line> = grid
+            .visible_rows(pane.scroll_offset)
+            .iter()
+            .map(|row| line_from_cells(row))
             .collect();
+        drop(grid);
 
-        let paragraph = Paragraph::new(lines)
-            .block(block)
-            .style(Style::default().fg(if i == 0 { Color::Green } else { Color::Yellow }))
-            .wrap(Wrap { trim: true });
-        
-        f.render_widget(paragraph, chunks[i]);
+        let paragraph = Paragraph::new(lines).block(block);
+        f.render_widget(paragraph, rect);
     }
 
     // Instructions
+    let mode_label = match app.input_mode {
+        InputMode::PassThrough => "PASS-THROUGH (Ctrl-B for commands)",
+        InputMode::Command => "COMMAND (Ctrl-B to return to pane)",
+    };
     let help_text = vec![
-        Line::from("q: Quit | v: Vertical | h: Horizontal | Tab: Switch focus | ↑↓: Scroll"),
-        Line::from("1: Disk Usage | 2: Network | 3: Memory | 4: CPU Info"),
+        Line::from(format!("-- {mode_label} --")),
+        Line::from("q: Quit | Tab: Switch focus | h/v: Split horiz/vert | x: Close pane"),
+        Line::from("↑↓: Scroll | Shift+arrows: Resize split | e: Command history"),
     ];
-    let help = Paragraph::new(help_text)
-        .style(Style::default().fg(Color::Gray));
+    let help = Paragraph::new(help_text).style(Style::default().fg(Color::Gray));
     f.render_widget(help, f.area());
-}
\ No newline at end of file
+
+    if app.history_overlay.open {
+        render_history_overlay(f, app);
+    }
+}
+
+/// Renders a centered popup listing the focused pane's recorded commands,
+/// colored green/red by exit status, with the current selection highlighted.
+fn render_history_overlay(f: &mut Frame, app: &App) {
+    let Some(pane) = app.panes.get(&app.focused) else {
+        return;
+    };
+    let entries = pane.history.lock().unwrap().entries.clone();
+
+    let area = f.area();
+    let width = (area.width * 3 / 5).max(20).min(area.width);
+    let height = (area.height * 3 / 5).max(6).min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width.saturating_sub(width)) / 2,
+        y: area.y + (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    let items: Vec<ListItem> = if entries.is_empty() {
+        vec![ListItem::new(
+            "No commands recorded yet (needs OSC 133 shell integration).",
+        )]
+    } else {
+        entries
+            .iter()
+            .map(|entry| {
+                let color = if entry.succeeded() {
+                    Color::Green
+                } else {
+                    Color::Red
+                };
+                let label = format!(
+                    "[{:>4}] {:<40} {:?}",
+                    entry.exit_code, entry.command, entry.duration
+                );
+                ListItem::new(Span::styled(label, Style::default().fg(color)))
+            })
+            .collect()
+    };
+
+    let mut state = ListState::default();
+    if !entries.is_empty() {
+        state.select(Some(app.history_overlay.selected.min(entries.len() - 1)));
+    }
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title("History (Enter: re-run, c: copy, Esc: close)")
+                .borders(Borders::ALL),
+        )
+        .highlight_style(Style::default().fg(Color::Black).bg(Color::Cyan));
+
+    f.render_widget(Clear, popup);
+    f.render_stateful_widget(list, popup, &mut state);
+}
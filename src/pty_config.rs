@@ -0,0 +1,76 @@
+//! Configuration for what `Terminal::spawn` runs: which program, with what
+//! arguments, in which directory, and with which environment overrides —
+//! replacing the previous hardcoded `CommandBuilder::new("zsh")`.
+
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub struct PtyConfig {
+    pub program: String,
+    pub args: Vec<String>,
+    pub cwd: Option<PathBuf>,
+    pub env: Vec<(String, String)>,
+    pub rows: u16,
+    pub cols: u16,
+}
+
+impl Default for PtyConfig {
+    fn default() -> Self {
+        Self {
+            program: default_shell(),
+            args: Vec::new(),
+            cwd: None,
+            env: vec![("TERM".to_string(), "xterm-256color".to_string())],
+            rows: 24,
+            cols: 80,
+        }
+    }
+}
+
+impl PtyConfig {
+    /// A config for running a specific command line via the default shell
+    /// (e.g. `PtyConfig::shell_command("cargo watch -x test")`), rather than
+    /// an interactive shell.
+    pub fn shell_command(command: impl Into<String>) -> Self {
+        Self {
+            args: vec!["-c".to_string(), command.into()],
+            ..Self::default()
+        }
+    }
+
+    pub fn with_cwd(mut self, cwd: impl Into<PathBuf>) -> Self {
+        self.cwd = Some(cwd.into());
+        self
+    }
+}
+
+/// The user's shell if set, falling back to a sensible default per OS.
+fn default_shell() -> String {
+    if let Ok(shell) = std::env::var("SHELL") {
+        if !shell.is_empty() {
+            return shell;
+        }
+    }
+    if cfg!(target_os = "windows") {
+        "cmd.exe".to_string()
+    } else {
+        "/bin/sh".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shell_command_wraps_with_dash_c() {
+        let cfg = PtyConfig::shell_command("echo hi");
+        assert_eq!(cfg.args, vec!["-c".to_string(), "echo hi".to_string()]);
+    }
+
+    #[test]
+    fn with_cwd_sets_the_directory() {
+        let cfg = PtyConfig::default().with_cwd("/tmp");
+        assert_eq!(cfg.cwd, Some(PathBuf::from("/tmp")));
+    }
+}